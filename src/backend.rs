@@ -0,0 +1,145 @@
+use anyhow::Result;
+use regex::Regex;
+
+/// Title, duration, and direct stream URL(s) a [`Backend`] resolved for a
+/// video, ready to hand to ffmpeg.
+///
+/// `audio` is only set when the backend resolved a split video+audio
+/// format (`--quality`/`--best`); otherwise the clip is a single
+/// progressive stream in `video`.
+pub struct ResolvedMedia {
+    pub title: String,
+    pub duration: Option<f64>,
+    pub video: String,
+    pub audio: Option<String>,
+}
+
+/// A site-specific way to recognize and resolve a URL ytclip can pull from.
+///
+/// yt-dlp itself already knows how to handle hundreds of sites, so
+/// [`resolve`](Backend::resolve) defaults to driving yt-dlp the same way
+/// for every backend; what varies per site today is mostly how we want to
+/// label it in logs. The seam exists so a site that needs different
+/// yt-dlp flags, a different format selector, or a non-yt-dlp resolution
+/// path entirely can override `resolve` without touching the generic
+/// path. The [`GenericBackend`] always matches and is tried last, so any
+/// URL yt-dlp understands still works even without a dedicated backend.
+pub trait Backend {
+    /// A short name for logging ("YouTube", "yt-dlp (generic)").
+    fn name(&self) -> &'static str;
+
+    /// Whether this backend claims the URL.
+    fn matches(&self, url: &str) -> bool;
+
+    /// Best-effort short id for logging (e.g. the YouTube video id).
+    /// `None` when the backend has no notion of a stable per-video id.
+    fn identify(&self, _url: &str) -> Option<String> {
+        None
+    }
+
+    /// Resolve `url` to a title, duration, and direct stream URL(s).
+    ///
+    /// `quality`/`best` select the format (capped height, or highest
+    /// available); `max_attempts`/`base_delay_ms` control retry-with-backoff
+    /// on transient yt-dlp failures. Defaults to the shared yt-dlp
+    /// `--dump-single-json` path; override for a backend that needs
+    /// different flags or a different resolution strategy.
+    fn resolve(
+        &self,
+        yt_dlp_bin: &str,
+        url: &str,
+        quality: Option<u32>,
+        best: bool,
+        max_attempts: u32,
+        base_delay_ms: u64,
+    ) -> Result<ResolvedMedia> {
+        let info = crate::fetch_metadata(yt_dlp_bin, url, quality, best, max_attempts, base_delay_ms)?;
+        let title = info.safe_title();
+        let duration = info.duration;
+        let (video, audio) = info.resolve_urls()?;
+        Ok(ResolvedMedia { title, duration, video, audio })
+    }
+}
+
+pub struct YoutubeBackend;
+
+impl Backend for YoutubeBackend {
+    fn name(&self) -> &'static str {
+        "YouTube"
+    }
+
+    fn matches(&self, url: &str) -> bool {
+        extract_video_id(url).is_some()
+    }
+
+    fn identify(&self, url: &str) -> Option<String> {
+        extract_video_id(url)
+    }
+}
+
+fn extract_video_id(url: &str) -> Option<String> {
+    // Try standard youtube.com format
+    let re = Regex::new(r"(?:youtube\.com/watch\?v=|youtu\.be/|youtube\.com/embed/)([a-zA-Z0-9_-]{11})").unwrap();
+
+    if let Some(captures) = re.captures(url) {
+        return captures.get(1).map(|m| m.as_str().to_string());
+    }
+
+    None
+}
+
+/// Falls back to yt-dlp's own site support for any URL no specific
+/// backend claimed (Vimeo, Twitch VODs, and hundreds of others).
+pub struct GenericBackend;
+
+impl Backend for GenericBackend {
+    fn name(&self) -> &'static str {
+        "yt-dlp (generic)"
+    }
+
+    fn matches(&self, _url: &str) -> bool {
+        true
+    }
+}
+
+/// Backends in priority order; `GenericBackend` is last and always matches.
+fn registry() -> Vec<Box<dyn Backend>> {
+    vec![Box::new(YoutubeBackend), Box::new(GenericBackend)]
+}
+
+/// Pick the first backend that claims `url`.
+pub fn resolve_backend(url: &str) -> Box<dyn Backend> {
+    registry()
+        .into_iter()
+        .find(|b| b.matches(url))
+        .expect("GenericBackend always matches")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_video_id() {
+        assert_eq!(
+            extract_video_id("https://www.youtube.com/watch?v=dQw4w9WgXcQ"),
+            Some("dQw4w9WgXcQ".to_string())
+        );
+        assert_eq!(
+            extract_video_id("https://youtu.be/dQw4w9WgXcQ"),
+            Some("dQw4w9WgXcQ".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_backend_dispatches_youtube_then_generic() {
+        assert_eq!(
+            resolve_backend("https://youtu.be/dQw4w9WgXcQ").name(),
+            "YouTube"
+        );
+        assert_eq!(
+            resolve_backend("https://vimeo.com/123456").name(),
+            "yt-dlp (generic)"
+        );
+    }
+}
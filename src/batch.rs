@@ -0,0 +1,112 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// A single clip request parsed from a batch manifest.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClipSpec {
+    pub start: String,
+    pub end: String,
+    #[serde(default)]
+    pub output: Option<String>,
+    #[serde(default = "default_speed")]
+    pub speed: f64,
+}
+
+fn default_speed() -> f64 {
+    1.0
+}
+
+/// Parse a batch manifest into a list of clip specs.
+///
+/// Supports three formats, chosen by file extension:
+/// - `.json`: a JSON array of clip objects
+/// - `.toml`: a TOML document with a top-level `clips` array of tables
+/// - anything else: one clip per line, `start,end[,output][,speed]`,
+///   blank lines and lines starting with `#` are ignored.
+pub fn parse_manifest(path: &str) -> Result<Vec<ClipSpec>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read batch manifest: {}", path))?;
+
+    match Path::new(path).extension().and_then(|e| e.to_str()) {
+        Some("json") => serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse JSON manifest: {}", path)),
+        Some("toml") => {
+            #[derive(Deserialize)]
+            struct TomlManifest {
+                clips: Vec<ClipSpec>,
+            }
+            let manifest: TomlManifest = toml::from_str(&contents)
+                .with_context(|| format!("Failed to parse TOML manifest: {}", path))?;
+            Ok(manifest.clips)
+        }
+        _ => parse_line_manifest(&contents),
+    }
+}
+
+fn parse_line_manifest(contents: &str) -> Result<Vec<ClipSpec>> {
+    let mut clips = Vec::new();
+
+    for (line_no, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split(',').map(|f| f.trim()).collect();
+        if fields.len() < 2 {
+            return Err(anyhow::anyhow!(
+                "Manifest line {}: expected at least `start,end`, got: {}",
+                line_no + 1,
+                line
+            ));
+        }
+
+        let output = fields.get(2).filter(|s| !s.is_empty()).map(|s| s.to_string());
+        let speed = match fields.get(3).filter(|s| !s.is_empty()) {
+            Some(s) => s
+                .parse::<f64>()
+                .with_context(|| format!("Manifest line {}: invalid speed: {}", line_no + 1, s))?,
+            None => default_speed(),
+        };
+
+        clips.push(ClipSpec {
+            start: fields[0].to_string(),
+            end: fields[1].to_string(),
+            output,
+            speed,
+        });
+    }
+
+    Ok(clips)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line_manifest() {
+        let contents = "\
+# a comment
+1:30,2:00
+90,120,clip.mp4
+1:00,1:30,fast.mp4,2.0
+";
+        let clips = parse_line_manifest(contents).unwrap();
+        assert_eq!(clips.len(), 3);
+        assert_eq!(clips[0].start, "1:30");
+        assert_eq!(clips[0].end, "2:00");
+        assert_eq!(clips[0].output, None);
+        assert_eq!(clips[0].speed, 1.0);
+        assert_eq!(clips[1].output, Some("clip.mp4".to_string()));
+        assert_eq!(clips[2].speed, 2.0);
+    }
+
+    #[test]
+    fn test_parse_line_manifest_rejects_malformed_line() {
+        let contents = "1:30\n";
+        assert!(parse_line_manifest(contents).is_err());
+    }
+}
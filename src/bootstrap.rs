@@ -0,0 +1,155 @@
+use anyhow::{Context, Result};
+use colored::*;
+use std::fs;
+use std::io::copy;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const YT_DLP_RELEASE_BASE: &str = "https://github.com/yt-dlp/yt-dlp/releases/latest/download";
+
+/// Resolve the yt-dlp executable to invoke.
+///
+/// Order of preference:
+/// 1. `override_path`, if given (`--yt-dlp-path`).
+/// 2. `yt-dlp` already on `PATH`.
+/// 3. A previously cached binary from an earlier bootstrap.
+/// 4. A fresh download into the cache dir, unless `no_download` is set.
+pub fn resolve_yt_dlp(override_path: Option<&str>, no_download: bool) -> Result<String> {
+    if let Some(path) = override_path {
+        return Ok(path.to_string());
+    }
+
+    if is_on_path("yt-dlp") {
+        return Ok("yt-dlp".to_string());
+    }
+
+    let cached = cached_binary_path()?;
+    if cached.exists() {
+        return Ok(cached.to_string_lossy().to_string());
+    }
+
+    if no_download {
+        return Err(anyhow::anyhow!(
+            "yt-dlp is not installed and --no-download was set. Install yt-dlp or drop the flag."
+        ));
+    }
+
+    download_yt_dlp(&cached)?;
+    Ok(cached.to_string_lossy().to_string())
+}
+
+fn is_on_path(bin: &str) -> bool {
+    Command::new(bin)
+        .arg("--version")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+fn cache_dir() -> Result<PathBuf> {
+    let base = dirs::cache_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine a cache directory for this platform"))?;
+    Ok(base.join("ytclip"))
+}
+
+fn cached_binary_path() -> Result<PathBuf> {
+    let name = if cfg!(windows) { "yt-dlp.exe" } else { "yt-dlp" };
+    Ok(cache_dir()?.join(name))
+}
+
+/// The yt-dlp release asset name for the current host OS/arch, per the
+/// naming scheme at https://github.com/yt-dlp/yt-dlp/releases.
+fn release_asset_name() -> Result<&'static str> {
+    if cfg!(target_os = "windows") {
+        Ok("yt-dlp.exe")
+    } else if cfg!(target_os = "macos") {
+        Ok("yt-dlp_macos")
+    } else if cfg!(all(target_os = "linux", target_arch = "aarch64")) {
+        Ok("yt-dlp_linux_aarch64")
+    } else if cfg!(all(target_os = "linux", target_arch = "x86_64")) {
+        Ok("yt-dlp_linux")
+    } else {
+        Err(anyhow::anyhow!(
+            "No prebuilt yt-dlp release for this OS/arch; install yt-dlp manually or pass --yt-dlp-path"
+        ))
+    }
+}
+
+fn download_yt_dlp(dest: &Path) -> Result<()> {
+    let asset = release_asset_name()?;
+    let url = format!("{}/{}", YT_DLP_RELEASE_BASE, asset);
+
+    println!("{} Downloading yt-dlp ({})...", "[INFO]".blue(), asset);
+
+    let dir = dest
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("Invalid cache path"))?;
+    fs::create_dir_all(dir).context("Failed to create yt-dlp cache directory")?;
+
+    let response = ureq::get(&url)
+        .call()
+        .with_context(|| format!("Failed to download yt-dlp from {}", url))?;
+
+    let mut file = fs::File::create(dest)
+        .with_context(|| format!("Failed to create {}", dest.display()))?;
+    copy(&mut response.into_reader(), &mut file)
+        .context("Failed to write downloaded yt-dlp binary")?;
+
+    mark_executable(dest)?;
+
+    println!("{} Cached yt-dlp at {}", "[INFO]".blue(), dest.display());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn mark_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut perms = fs::metadata(path)?.permissions();
+    perms.set_mode(0o755);
+    fs::set_permissions(path, perms)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn mark_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_release_asset_name_matches_current_target() {
+        let name = release_asset_name().unwrap();
+        if cfg!(target_os = "windows") {
+            assert_eq!(name, "yt-dlp.exe");
+        } else if cfg!(target_os = "macos") {
+            assert_eq!(name, "yt-dlp_macos");
+        } else if cfg!(all(target_os = "linux", target_arch = "aarch64")) {
+            assert_eq!(name, "yt-dlp_linux_aarch64");
+        } else if cfg!(all(target_os = "linux", target_arch = "x86_64")) {
+            assert_eq!(name, "yt-dlp_linux");
+        }
+    }
+
+    #[test]
+    fn test_cached_binary_path_is_under_cache_dir_with_platform_name() {
+        let cache = cache_dir().unwrap();
+        let cached = cached_binary_path().unwrap();
+
+        assert_eq!(cached.parent(), Some(cache.as_path()));
+        if cfg!(windows) {
+            assert_eq!(cached.file_name().unwrap(), "yt-dlp.exe");
+        } else {
+            assert_eq!(cached.file_name().unwrap(), "yt-dlp");
+        }
+    }
+
+    #[test]
+    fn test_cache_dir_is_namespaced_to_ytclip() {
+        let cache = cache_dir().unwrap();
+        assert_eq!(cache.file_name().unwrap(), "ytclip");
+    }
+}
@@ -1,32 +1,89 @@
+mod backend;
+mod batch;
+mod bootstrap;
+mod metadata;
+mod progress;
+mod retry;
+
 use anyhow::{Context, Result};
+use batch::ClipSpec;
 use clap::Parser;
 use colored::*;
-use regex::Regex;
+use metadata::VideoMetadata;
+use retry::Attempt;
 use std::process::Command;
 
-/// Fast and efficient YouTube video clip downloader
+/// Fast and efficient clip downloader for YouTube and anything else yt-dlp supports
 #[derive(Parser, Debug)]
 #[command(name = "ytclip")]
 #[command(author = "Eden Chan")]
 #[command(version = "1.0.0")]
-#[command(about = "Download specific clips from YouTube videos", long_about = None)]
+#[command(about = "Download specific clips from YouTube, Vimeo, Twitch, and other yt-dlp-supported sites", long_about = None)]
 struct Args {
-    /// YouTube URL to download from
-    url: String,
+    /// URL to download from (YouTube, Vimeo, Twitch VODs, or anything else yt-dlp supports)
+    url: Option<String>,
 
     /// Start time (e.g., 1:30, 90, 1:30:45)
-    start_time: String,
+    #[arg(conflicts_with = "batch")]
+    start_time: Option<String>,
 
     /// End time (e.g., 2:45, 165, 2:45:30)
-    end_time: String,
+    #[arg(conflicts_with = "batch")]
+    end_time: Option<String>,
 
     /// Custom output filename (optional)
-    #[arg(short, long)]
+    #[arg(short, long, conflicts_with = "batch")]
     output: Option<String>,
 
     /// Playback speed (0.5 to 4.0)
-    #[arg(short, long, default_value = "1.0")]
+    #[arg(short, long, default_value = "1.0", conflicts_with = "batch")]
     speed: f64,
+
+    /// Read a batch manifest and clip multiple ranges from the same URL
+    /// in one run. Conflicts with start_time/end_time/--speed/--output:
+    /// each clip's times, speed, and output filename come from its
+    /// manifest entry instead, so passing both is a hard error rather
+    /// than one silently overriding the other.
+    #[arg(long)]
+    batch: Option<String>,
+
+    /// Maximum video height to request (e.g. 1080, 2160). Pulls separate
+    /// video/audio streams and muxes them, unlocking resolutions above
+    /// the muxed "best[ext=mp4]/best" progressive stream.
+    #[arg(long)]
+    quality: Option<u32>,
+
+    /// Request the highest available video/audio streams regardless of
+    /// height, muxing them together like --quality
+    #[arg(long)]
+    best: bool,
+
+    /// Max attempts for transient yt-dlp failures before giving up
+    #[arg(long, default_value = "5")]
+    retries: u32,
+
+    /// Initial backoff delay in milliseconds, doubled after each retry
+    /// (capped at 30s) and jittered by up to ±25%
+    #[arg(long, default_value = "500")]
+    retry_delay: u64,
+
+    /// Use this yt-dlp binary instead of searching PATH / the cache dir
+    #[arg(long)]
+    yt_dlp_path: Option<String>,
+
+    /// Don't download yt-dlp automatically if it's missing; fail instead
+    #[arg(long)]
+    no_download: bool,
+
+    /// Print the video's title, id, duration, and uploader, then exit
+    /// without downloading anything
+    #[arg(long)]
+    print_info: bool,
+
+    /// Don't show a live ffmpeg progress bar; stream its normal stderr
+    /// output instead
+    #[arg(long)]
+    no_progress: bool,
 }
 
 fn parse_time(time_str: &str) -> Result<f64> {
@@ -62,50 +119,100 @@ fn parse_time(time_str: &str) -> Result<f64> {
     Ok(seconds)
 }
 
-fn extract_video_id(url: &str) -> Option<String> {
-    // Try standard youtube.com format
-    let re = Regex::new(r"(?:youtube\.com/watch\?v=|youtu\.be/|youtube\.com/embed/)([a-zA-Z0-9_-]{11})").unwrap();
+/// Whether a yt-dlp failure is a clean, permanent refusal (e.g. the video
+/// was removed or is geo-blocked) rather than a transient hiccup worth
+/// retrying.
+fn is_fatal_yt_dlp_message(text: &str) -> bool {
+    let lower = text.to_lowercase();
+    lower.contains("video unavailable")
+        || lower.contains("this video is private")
+        || lower.contains("has been removed")
+}
 
-    if let Some(captures) = re.captures(url) {
-        return captures.get(1).map(|m| m.as_str().to_string());
-    }
+/// Run `yt-dlp` with the given args, retrying transient failures
+/// (non-zero exit, empty stdout) with exponential backoff. A clean
+/// "video unavailable"-style message is treated as fatal and returned
+/// immediately without consuming retries.
+fn run_yt_dlp(
+    yt_dlp_bin: &str,
+    args: &[&str],
+    max_attempts: u32,
+    base_delay_ms: u64,
+) -> Result<std::process::Output> {
+    retry::retry_with_backoff(max_attempts, base_delay_ms, |_attempt| {
+        let output = match Command::new(yt_dlp_bin).args(args).output() {
+            Ok(output) => output,
+            Err(e) => return Attempt::Fatal(anyhow::Error::new(e).context("Failed to execute yt-dlp. Is it installed?")),
+        };
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        let failed = !output.status.success() || stdout.trim().is_empty();
+        if failed && (is_fatal_yt_dlp_message(&stdout) || is_fatal_yt_dlp_message(&stderr)) {
+            return Attempt::Fatal(anyhow::anyhow!("yt-dlp reported the video is unavailable"));
+        }
 
-    None
-}
+        if !output.status.success() {
+            return Attempt::Retryable(anyhow::anyhow!(
+                "yt-dlp exited with status {}",
+                output.status
+            ));
+        }
 
-fn get_video_title(url: &str) -> Result<String> {
-    println!("{}", "[INFO] Fetching video title...".blue());
+        if stdout.trim().is_empty() {
+            return Attempt::Retryable(anyhow::anyhow!("yt-dlp returned empty output"));
+        }
 
-    let output = Command::new("yt-dlp")
-        .args(&["--get-title", "--no-playlist", url])
-        .output()
-        .context("Failed to execute yt-dlp. Is it installed?")?;
+        Attempt::Done(output)
+    })
+}
 
-    if !output.status.success() {
-        return Ok("video".to_string());
-    }
+/// Fetch title, id, duration, uploader, and resolved format URL(s) for a
+/// video in a single `yt-dlp --dump-single-json` call.
+///
+/// This is the default resolution strategy shared by every [`backend::Backend`];
+/// visible to the `backend` module so a backend's default `resolve` can call it.
+pub(crate) fn fetch_metadata(
+    yt_dlp_bin: &str,
+    url: &str,
+    quality: Option<u32>,
+    best: bool,
+    max_attempts: u32,
+    base_delay_ms: u64,
+) -> Result<VideoMetadata> {
+    println!("{}", "[INFO] Fetching video metadata...".blue());
+
+    let format = if best {
+        "bestvideo+bestaudio".to_string()
+    } else if let Some(height) = quality {
+        format!("bestvideo[height<={}]+bestaudio", height)
+    } else {
+        "best[ext=mp4]/best".to_string()
+    };
 
-    let title = String::from_utf8_lossy(&output.stdout)
-        .trim()
-        .to_string();
-
-    // Clean filename
-    let safe_title = title
-        .chars()
-        .map(|c| match c {
-            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
-            _ => c
-        })
-        .collect::<String>()
-        .split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ");
-
-    Ok(safe_title)
+    let output = run_yt_dlp(
+        yt_dlp_bin,
+        &["--dump-single-json", "--no-playlist", "-f", &format, url],
+        max_attempts,
+        base_delay_ms,
+    )
+    .context("Failed to fetch video metadata with yt-dlp")?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    metadata::parse_metadata(&stdout)
 }
 
+/// Build the ffmpeg arg list for one clip.
+///
+/// When `audio_url` is `Some`, `url` is treated as a video-only stream and
+/// a second `-i` is added for the audio, with explicit `-map` flags to
+/// select video from the first input and audio from the second. This is
+/// how separately-fetched DASH video/audio representations get muxed
+/// together; the single-URL path is unchanged otherwise.
 fn build_ffmpeg_command(
     url: &str,
+    audio_url: Option<&str>,
     start_seconds: f64,
     duration: f64,
     output_file: &str,
@@ -116,10 +223,25 @@ fn build_ffmpeg_command(
         start_seconds.to_string(),
         "-i".to_string(),
         url.to_string(),
-        "-t".to_string(),
-        duration.to_string(),
     ];
 
+    if let Some(audio_url) = audio_url {
+        args.push("-ss".to_string());
+        args.push(start_seconds.to_string());
+        args.push("-i".to_string());
+        args.push(audio_url.to_string());
+    }
+
+    args.push("-t".to_string());
+    args.push(duration.to_string());
+
+    if audio_url.is_some() {
+        args.push("-map".to_string());
+        args.push("0:v".to_string());
+        args.push("-map".to_string());
+        args.push("1:a".to_string());
+    }
+
     // Add speed adjustment if needed
     if (speed - 1.0).abs() > 0.01 {
         let video_filter = format!("setpts={:.2}*PTS", 1.0 / speed);
@@ -162,14 +284,49 @@ fn build_ffmpeg_command(
     args
 }
 
-fn download_clip(
-    url: &str,
+/// Direct stream URL(s) resolved for a video.
+///
+/// `audio` is only set when the backend resolved a split video+audio
+/// format (`--quality`/`--best`); otherwise the clip is a single
+/// progressive stream in `video`. Built from a `backend::ResolvedMedia`.
+struct ResolvedUrls {
+    video: String,
+    audio: Option<String>,
+}
+
+fn default_output_name(title: &str, start_time: &str, end_time: &str, speed: f64) -> String {
+    if (speed - 1.0).abs() > 0.01 {
+        format!("{}_clip_{}-{}_{}x.mp4",
+                title,
+                start_time.replace(':', "-"),
+                end_time.replace(':', "-"),
+                speed)
+    } else {
+        format!("{}_clip_{}_{}.mp4",
+                title,
+                start_time.replace(':', "-"),
+                end_time.replace(':', "-"))
+    }
+}
+
+/// Clip and encode a single range out of an already-resolved direct URL.
+///
+/// Shared by the single-clip path and the batch path so both go through
+/// the same time parsing, speed validation, and ffmpeg invocation.
+fn process_one_clip(
+    urls: &ResolvedUrls,
+    title: &str,
+    video_duration: Option<f64>,
     start_time: &str,
     end_time: &str,
     output_name: Option<String>,
     speed: f64,
-) -> Result<()> {
-    // Parse times
+    show_progress: bool,
+) -> Result<String> {
+    if speed < 0.5 || speed > 4.0 {
+        return Err(anyhow::anyhow!("Speed must be between 0.5 and 4.0"));
+    }
+
     let start_seconds = parse_time(start_time)?;
     let end_seconds = parse_time(end_time)?;
 
@@ -177,13 +334,18 @@ fn download_clip(
         return Err(anyhow::anyhow!("End time must be after start time"));
     }
 
-    let duration = end_seconds - start_seconds;
+    if let Some(video_duration) = video_duration {
+        if end_seconds > video_duration {
+            return Err(anyhow::anyhow!(
+                "End time {:.1}s exceeds video duration {:.1}s",
+                end_seconds,
+                video_duration
+            ));
+        }
+    }
 
-    // Get video ID
-    let video_id = extract_video_id(url)
-        .ok_or_else(|| anyhow::anyhow!("Could not extract video ID from URL"))?;
+    let duration = end_seconds - start_seconds;
 
-    println!("{} Video ID: {}", "[INFO]".blue(), video_id);
     println!("{} Clipping from {} to {} (duration: {:.1}s)",
              "[TIME]".yellow(), start_time, end_time, duration);
 
@@ -191,85 +353,196 @@ fn download_clip(
         println!("{} Speed: {:.1}x", "[SPEED]".magenta(), speed);
     }
 
-    // Get video title for output filename
-    let title = get_video_title(url).unwrap_or_else(|_| "video".to_string());
+    let output_file = output_name
+        .unwrap_or_else(|| default_output_name(title, start_time, end_time, speed));
 
-    let output_file = output_name.unwrap_or_else(|| {
-        if (speed - 1.0).abs() > 0.01 {
-            format!("{}_clip_{}-{}_{}x.mp4",
-                    title,
-                    start_time.replace(':', "-"),
-                    end_time.replace(':', "-"),
-                    speed)
-        } else {
-            format!("{}_clip_{}_{}.mp4",
-                    title,
-                    start_time.replace(':', "-"),
-                    end_time.replace(':', "-"))
-        }
-    });
-
-    println!("{} Streaming clip...", "[INFO]".blue());
-
-    // Get direct URL using yt-dlp
-    let direct_url_output = Command::new("yt-dlp")
-        .args(&[
-            "--no-playlist",
-            "-f", "best[ext=mp4]/best",
-            "--get-url",
-            url
-        ])
-        .output()
-        .context("Failed to get video URL with yt-dlp")?;
-
-    if !direct_url_output.status.success() {
-        return Err(anyhow::anyhow!("Failed to extract video URL"));
-    }
-
-    let direct_url = String::from_utf8_lossy(&direct_url_output.stdout)
-        .trim()
-        .to_string();
-
-    // Build and run ffmpeg command
     let ffmpeg_args = build_ffmpeg_command(
-        &direct_url,
+        &urls.video,
+        urls.audio.as_deref(),
         start_seconds,
         duration,
         &output_file,
         speed,
     );
 
-    let status = Command::new("ffmpeg")
-        .args(&ffmpeg_args)
-        .status()
-        .context("Failed to execute ffmpeg. Is it installed?")?;
-
-    if !status.success() {
-        return Err(anyhow::anyhow!("FFmpeg failed to process the video"));
-    }
+    progress::run_ffmpeg(&ffmpeg_args, duration, show_progress)?;
 
     println!("{} Clip saved as: {}",
              "[SUCCESS]".green().bold(),
              output_file.cyan());
 
+    Ok(output_file)
+}
+
+fn log_source(source: &dyn backend::Backend, url: &str) {
+    match source.identify(url) {
+        Some(id) => println!("{} {} ID: {}", "[INFO]".blue(), source.name(), id),
+        None => println!("{} Source: {}", "[INFO]".blue(), source.name()),
+    }
+}
+
+fn download_clip(
+    yt_dlp_bin: &str,
+    url: &str,
+    start_time: &str,
+    end_time: &str,
+    output_name: Option<String>,
+    speed: f64,
+    quality: Option<u32>,
+    best: bool,
+    retries: u32,
+    retry_delay: u64,
+    show_progress: bool,
+) -> Result<()> {
+    let source = backend::resolve_backend(url);
+    log_source(source.as_ref(), url);
+
+    let media = source.resolve(yt_dlp_bin, url, quality, best, retries, retry_delay)?;
+    let urls = ResolvedUrls { video: media.video, audio: media.audio };
+    if urls.audio.is_some() {
+        println!("{} Fetched separate video/audio streams", "[INFO]".blue());
+    }
+
+    println!("{} Streaming clip...", "[INFO]".blue());
+
+    process_one_clip(
+        &urls,
+        &media.title,
+        media.duration,
+        start_time,
+        end_time,
+        output_name,
+        speed,
+        show_progress,
+    )?;
+
+    Ok(())
+}
+
+/// Run every clip in a batch manifest against a single source video,
+/// fetching the title and direct URL only once. A failure on one clip
+/// is reported but does not abort the remaining clips.
+fn download_batch(
+    yt_dlp_bin: &str,
+    url: &str,
+    clips: Vec<ClipSpec>,
+    quality: Option<u32>,
+    best: bool,
+    retries: u32,
+    retry_delay: u64,
+    show_progress: bool,
+) -> Result<()> {
+    let source = backend::resolve_backend(url);
+    log_source(source.as_ref(), url);
+    println!("{} {} clip(s) in batch", "[INFO]".blue(), clips.len());
+
+    let media = source.resolve(yt_dlp_bin, url, quality, best, retries, retry_delay)?;
+    let urls = ResolvedUrls { video: media.video, audio: media.audio };
+    if urls.audio.is_some() {
+        println!("{} Fetched separate video/audio streams", "[INFO]".blue());
+    }
+
+    println!("{} Streaming clip...", "[INFO]".blue());
+
+    let mut failures = 0;
+    for (i, clip) in clips.iter().enumerate() {
+        println!("{} Clip {}/{}", "[BATCH]".blue(), i + 1, clips.len());
+
+        let result = process_one_clip(
+            &urls,
+            &media.title,
+            media.duration,
+            &clip.start,
+            &clip.end,
+            clip.output.clone(),
+            clip.speed,
+            show_progress,
+        );
+
+        if let Err(e) = result {
+            failures += 1;
+            eprintln!("{} Clip {}/{} failed: {}", "[ERROR]".red(), i + 1, clips.len(), e);
+        }
+    }
+
+    if failures > 0 {
+        println!("{} {}/{} clips failed", "[WARN]".yellow(), failures, clips.len());
+    }
+
     Ok(())
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
 
+    let url = args
+        .url
+        .ok_or_else(|| anyhow::anyhow!("A URL is required"))?;
+
+    let yt_dlp_bin = bootstrap::resolve_yt_dlp(args.yt_dlp_path.as_deref(), args.no_download)?;
+
+    if args.print_info {
+        let info = fetch_metadata(
+            &yt_dlp_bin,
+            &url,
+            args.quality,
+            args.best,
+            args.retries,
+            args.retry_delay,
+        )?;
+        println!("{} {}", "Title:".blue(), info.title);
+        println!("{} {}", "ID:".blue(), info.id);
+        if let Some(duration) = info.duration {
+            println!("{} {:.1}s", "Duration:".blue(), duration);
+        }
+        if let Some(uploader) = &info.uploader {
+            println!("{} {}", "Uploader:".blue(), uploader);
+        }
+        return Ok(());
+    }
+
+    if let Some(manifest_path) = args.batch {
+        let clips = batch::parse_manifest(&manifest_path)?;
+        if clips.is_empty() {
+            return Err(anyhow::anyhow!("Batch manifest contains no clips"));
+        }
+        return download_batch(
+            &yt_dlp_bin,
+            &url,
+            clips,
+            args.quality,
+            args.best,
+            args.retries,
+            args.retry_delay,
+            !args.no_progress,
+        );
+    }
+
     // Validate speed
     if args.speed < 0.5 || args.speed > 4.0 {
         return Err(anyhow::anyhow!("Speed must be between 0.5 and 4.0"));
     }
 
+    let start_time = args
+        .start_time
+        .ok_or_else(|| anyhow::anyhow!("A start time is required"))?;
+    let end_time = args
+        .end_time
+        .ok_or_else(|| anyhow::anyhow!("An end time is required"))?;
+
     // Download the clip
     download_clip(
-        &args.url,
-        &args.start_time,
-        &args.end_time,
+        &yt_dlp_bin,
+        &url,
+        &start_time,
+        &end_time,
         args.output,
         args.speed,
+        args.quality,
+        args.best,
+        args.retries,
+        args.retry_delay,
+        !args.no_progress,
     )?;
 
     Ok(())
@@ -286,15 +559,4 @@ mod tests {
         assert_eq!(parse_time("1:30:45").unwrap(), 5445.0);
     }
 
-    #[test]
-    fn test_extract_video_id() {
-        assert_eq!(
-            extract_video_id("https://www.youtube.com/watch?v=dQw4w9WgXcQ"),
-            Some("dQw4w9WgXcQ".to_string())
-        );
-        assert_eq!(
-            extract_video_id("https://youtu.be/dQw4w9WgXcQ"),
-            Some("dQw4w9WgXcQ".to_string())
-        );
-    }
 }
\ No newline at end of file
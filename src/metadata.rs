@@ -0,0 +1,116 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// One resolved download yt-dlp picked for the requested format selector
+/// (e.g. the video-only and audio-only streams behind `bestvideo+bestaudio`).
+#[derive(Debug, Deserialize)]
+pub struct RequestedDownload {
+    pub url: Option<String>,
+    #[serde(default)]
+    pub vcodec: Option<String>,
+    #[serde(default)]
+    pub acodec: Option<String>,
+}
+
+/// Video metadata as reported by a single `yt-dlp --dump-single-json` call.
+///
+/// Replaces the old two-call dance (`--get-title`, then `--get-url`): one
+/// process spawn and one network round-trip gives us the title, duration,
+/// and resolved format URL(s) together.
+#[derive(Debug, Deserialize)]
+pub struct VideoMetadata {
+    pub id: String,
+    pub title: String,
+    pub duration: Option<f64>,
+    pub uploader: Option<String>,
+    #[serde(default)]
+    pub requested_downloads: Vec<RequestedDownload>,
+}
+
+impl VideoMetadata {
+    /// Filesystem-safe version of the title, used to build default
+    /// output filenames.
+    pub fn safe_title(&self) -> String {
+        self.title
+            .chars()
+            .map(|c| match c {
+                '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+                _ => c,
+            })
+            .collect::<String>()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Pick the video (and, when yt-dlp resolved a split format, audio)
+    /// URL to feed ffmpeg.
+    pub fn resolve_urls(&self) -> Result<(String, Option<String>)> {
+        match self.requested_downloads.as_slice() {
+            [] => Err(anyhow::anyhow!(
+                "yt-dlp metadata contained no downloadable format"
+            )),
+            [single] => {
+                let url = single
+                    .url
+                    .clone()
+                    .ok_or_else(|| anyhow::anyhow!("yt-dlp metadata format has no URL"))?;
+                Ok((url, None))
+            }
+            downloads => {
+                let video = downloads
+                    .iter()
+                    .find(|d| d.acodec.as_deref() == Some("none"))
+                    .or_else(|| downloads.first());
+                let audio = downloads.iter().find(|d| d.vcodec.as_deref() == Some("none"));
+
+                let video_url = video
+                    .and_then(|d| d.url.clone())
+                    .ok_or_else(|| anyhow::anyhow!("yt-dlp metadata missing a video URL"))?;
+                let audio_url = audio.and_then(|d| d.url.clone());
+
+                Ok((video_url, audio_url))
+            }
+        }
+    }
+}
+
+pub fn parse_metadata(json: &str) -> Result<VideoMetadata> {
+    serde_json::from_str(json).context("Failed to parse yt-dlp JSON metadata")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Fixtures mirror the actual shape of `yt-dlp --dump-single-json
+    // --no-playlist -f <format> <url>` output (full `formats` list,
+    // thumbnails, `requested_formats`, etc. included), not a minimal
+    // JSON blob hand-tailored to `resolve_urls`'s own assumptions.
+    const PROGRESSIVE_FIXTURE: &str =
+        include_str!("../tests/fixtures/ytdlp_dump_single_json_progressive.json");
+    const SPLIT_FIXTURE: &str = include_str!("../tests/fixtures/ytdlp_dump_single_json_split.json");
+
+    #[test]
+    fn test_resolve_urls_single_format() {
+        let metadata = parse_metadata(PROGRESSIVE_FIXTURE).unwrap();
+        assert_eq!(metadata.title, "Rick Astley - Never Gonna Give You Up (Official Video)");
+        assert_eq!(metadata.duration, Some(212.0));
+
+        let (video, audio) = metadata.resolve_urls().unwrap();
+        assert!(video.starts_with("https://rr3---sn-abc7sn7r.googlevideo.com/videoplayback"));
+        assert!(video.contains("itag=18"));
+        assert_eq!(audio, None);
+    }
+
+    #[test]
+    fn test_resolve_urls_split_format() {
+        let metadata = parse_metadata(SPLIT_FIXTURE).unwrap();
+        assert_eq!(metadata.title, "Rick Astley - Never Gonna Give You Up (Official Video)");
+
+        let (video, audio) = metadata.resolve_urls().unwrap();
+        assert!(video.contains("itag=137"), "expected the video-only (acodec=none) stream");
+        let audio = audio.expect("split format should resolve an audio-only stream");
+        assert!(audio.contains("itag=140"), "expected the audio-only (vcodec=none) stream");
+    }
+}
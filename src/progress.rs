@@ -0,0 +1,88 @@
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use std::io::{BufRead, BufReader, IsTerminal};
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+
+/// Run ffmpeg for one clip, with a live progress bar when possible.
+///
+/// Spawns ffmpeg with `-progress pipe:1 -nostats` and parses the
+/// `out_time_ms=` lines it emits on stdout against the known clip
+/// `duration_secs` to drive a percentage/ETA bar, which is cleared on
+/// completion so the `[SUCCESS]` line printed by the caller stays clean.
+/// Falls back to the plain inherited-stderr behavior when `show_progress`
+/// is false or stdout isn't a terminal.
+pub fn run_ffmpeg(args: &[String], duration_secs: f64, show_progress: bool) -> Result<()> {
+    if !show_progress || !std::io::stdout().is_terminal() {
+        let status = Command::new("ffmpeg")
+            .args(args)
+            .status()
+            .context("Failed to execute ffmpeg. Is it installed?")?;
+        return check_status(status, &[]);
+    }
+
+    let mut piped_args = vec![
+        "-progress".to_string(),
+        "pipe:1".to_string(),
+        "-nostats".to_string(),
+    ];
+    piped_args.extend_from_slice(args);
+
+    let mut child = Command::new("ffmpeg")
+        .args(&piped_args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to execute ffmpeg. Is it installed?")?;
+
+    let stdout = child.stdout.take().expect("ffmpeg stdout was piped");
+    let stderr = child.stderr.take().expect("ffmpeg stderr was piped");
+
+    // ffmpeg's diagnostics (codec errors, missing streams, disk-full,
+    // etc.) all go to stderr; read it on its own thread so it can't
+    // block waiting for us while we're busy reading stdout progress.
+    let stderr_reader = thread::spawn(move || {
+        BufReader::new(stderr)
+            .lines()
+            .filter_map(|line| line.ok())
+            .collect::<Vec<String>>()
+    });
+
+    let reader = BufReader::new(stdout);
+
+    let bar = ProgressBar::new(100);
+    bar.set_style(
+        ProgressStyle::with_template("{spinner:.green} [{bar:40.cyan/blue}] {percent}% (ETA {eta})")
+            .unwrap()
+            .progress_chars("=>-"),
+    );
+
+    for line in reader.lines() {
+        let line = line.context("Failed to read ffmpeg progress output")?;
+        // ffmpeg's `out_time_ms=` field is actually microseconds, a
+        // long-standing naming quirk in its -progress output.
+        if let Some(value) = line.strip_prefix("out_time_ms=") {
+            if let Ok(out_time_us) = value.trim().parse::<i64>() {
+                let elapsed_secs = (out_time_us.max(0) as f64) / 1_000_000.0;
+                let pct = ((elapsed_secs / duration_secs) * 100.0).clamp(0.0, 100.0);
+                bar.set_position(pct as u64);
+            }
+        }
+    }
+
+    let status = child.wait().context("Failed to wait on ffmpeg")?;
+    bar.finish_and_clear();
+
+    let stderr_lines = stderr_reader.join().unwrap_or_default();
+    check_status(status, &stderr_lines)
+}
+
+fn check_status(status: ExitStatus, stderr_lines: &[String]) -> Result<()> {
+    if !status.success() {
+        if !stderr_lines.is_empty() {
+            eprintln!("{}", stderr_lines.join("\n"));
+        }
+        return Err(anyhow::anyhow!("FFmpeg failed to process the video"));
+    }
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use anyhow::Result;
+use colored::*;
+use rand::Rng;
+use std::thread;
+use std::time::Duration;
+
+const MAX_DELAY_MS: u64 = 30_000;
+
+/// Outcome of a single attempt inside `retry_with_backoff`.
+pub enum Attempt<T> {
+    /// The operation succeeded.
+    Done(T),
+    /// The operation failed but may succeed if retried (e.g. throttling,
+    /// a transient network error, or yt-dlp returning no output).
+    Retryable(anyhow::Error),
+    /// The operation failed in a way that retrying cannot fix (e.g. the
+    /// video is unavailable); stop immediately.
+    Fatal(anyhow::Error),
+}
+
+/// Retry an operation with exponential backoff and jitter.
+///
+/// Delay starts at `base_delay_ms`, doubles after each retryable failure,
+/// gains up to ±25% jitter, and is capped at 30s. Gives up after
+/// `max_attempts` retryable failures, or immediately on a `Fatal`
+/// outcome.
+pub fn retry_with_backoff<T>(
+    max_attempts: u32,
+    base_delay_ms: u64,
+    mut op: impl FnMut(u32) -> Attempt<T>,
+) -> Result<T> {
+    let mut delay_ms = base_delay_ms;
+
+    for attempt_num in 1..=max_attempts.max(1) {
+        match op(attempt_num) {
+            Attempt::Done(value) => return Ok(value),
+            Attempt::Fatal(e) => return Err(e),
+            Attempt::Retryable(e) => {
+                if attempt_num >= max_attempts {
+                    return Err(e);
+                }
+
+                let jitter = 1.0 + rand::thread_rng().gen_range(-0.25..=0.25);
+                let sleep_ms = ((delay_ms as f64) * jitter).round() as u64;
+                let sleep_ms = sleep_ms.min(MAX_DELAY_MS);
+
+                println!(
+                    "{} attempt {}/{} failed ({}), retrying in {}ms...",
+                    "[RETRY]".yellow(),
+                    attempt_num,
+                    max_attempts,
+                    e,
+                    sleep_ms
+                );
+
+                thread::sleep(Duration::from_millis(sleep_ms));
+                delay_ms = (delay_ms * 2).min(MAX_DELAY_MS);
+            }
+        }
+    }
+
+    unreachable!("loop always returns on its final attempt")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_fatal_short_circuits_without_retrying() {
+        let calls = Cell::new(0);
+        let result: Result<()> = retry_with_backoff(5, 1, |_attempt| {
+            calls.set(calls.get() + 1);
+            Attempt::Fatal(anyhow::anyhow!("video unavailable"))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn test_gives_up_after_exactly_max_attempts() {
+        let calls = Cell::new(0);
+        let result: Result<()> = retry_with_backoff(3, 1, |_attempt| {
+            calls.set(calls.get() + 1);
+            Attempt::Retryable(anyhow::anyhow!("transient failure"))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn test_succeeds_after_retrying() {
+        let calls = Cell::new(0);
+        let result = retry_with_backoff(5, 1, |_attempt| {
+            calls.set(calls.get() + 1);
+            if calls.get() < 3 {
+                Attempt::Retryable(anyhow::anyhow!("transient failure"))
+            } else {
+                Attempt::Done(42)
+            }
+        });
+
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.get(), 3);
+    }
+}